@@ -1,6 +1,6 @@
 //! Parallelization contracts, describing requirements for data movement along dataflow edges.
 //!
-//! Pacts describe how data should be exchanged between workers, and implement a method which 
+//! Pacts describe how data should be exchanged between workers, and implement a method which
 //! creates a pair of `Push` and `Pull` implementors from an `A: Allocate`. These two endpoints
 //! respectively distribute and collect data among workers according to the pact.
 //!
@@ -8,36 +8,98 @@
 //! The progress tracking logic assumes that this number is independent of the pact used.
 
 use std::marker::PhantomData;
+use std::rc::Rc;
+use std::cell::RefCell;
 
 use timely_communication::{Allocate, Push, Pull, Data};
 use timely_communication::allocator::Thread;
 
-use dataflow::channels::pushers::Exchange as ExchangePusher;
-use dataflow::channels::{Message, Content};
+use dataflow::channels::Message;
+use logging::{Logger, MessagesEvent};
 
 use abomonation::Abomonation;
 
+/// A batch of records exchanged between workers as a single unit.
+pub trait Container {
+    /// The type of an individual record held by the container.
+    type Item;
+    /// The number of records currently held.
+    fn len(&self) -> usize;
+    /// Whether the container holds no records.
+    fn is_empty(&self) -> bool { self.len() == 0 }
+}
+
+impl<D> Container for Vec<D> {
+    type Item = D;
+    fn len(&self) -> usize { Vec::len(self) }
+}
+
+/// A `Container` that can distribute its records across several destination containers.
+pub trait PushPartitioned: Container {
+    /// Routes each record of `self` to `buffers[index(record)]`, calling `flush(dest, buffer)`
+    /// whenever a destination buffer fills up, and once more at the end for any buffers that
+    /// still hold records. `flush` is responsible for draining `buffer` (e.g. via
+    /// `mem::replace`); `push_partitioned` does not clear it, so a `flush` that fails to empty
+    /// the buffer will cause it to be flushed again and again as it keeps growing.
+    fn push_partitioned<I, F>(&mut self, buffers: &mut [Self], index: I, flush: F)
+    where
+        I: FnMut(&Self::Item) -> usize,
+        F: FnMut(usize, &mut Self),
+        Self: Sized;
+}
+
+/// Records buffered per destination before `push_partitioned` eagerly flushes it.
+const PARTITION_BUFFER_SIZE: usize = 1024;
+
+impl<D> PushPartitioned for Vec<D> {
+    fn push_partitioned<I, F>(&mut self, buffers: &mut [Self], mut index: I, mut flush: F)
+    where
+        I: FnMut(&D) -> usize,
+        F: FnMut(usize, &mut Self),
+    {
+        for item in self.drain(..) {
+            let dest = index(&item);
+            buffers[dest].push(item);
+            if buffers[dest].len() >= PARTITION_BUFFER_SIZE {
+                flush(dest, &mut buffers[dest]);
+            }
+        }
+        for (dest, buffer) in buffers.iter_mut().enumerate() {
+            if !buffer.is_empty() {
+                flush(dest, buffer);
+            }
+        }
+    }
+}
+
 /// A ParallelizationContract allocates paired `Push` and `Pull` implementors.
-pub trait ParallelizationContract<T: 'static, D: 'static> {
+pub trait ParallelizationContract<T: 'static, C: Container+'static> {
     /// Type implementing `Push` produced by this pact.
-    type Pusher: Push<(T, Content<D>)>+'static;
+    type Pusher: Push<(T, C)>+'static;
     /// Type implementing `Pull` produced by this pact.
-    type Puller: Pull<(T, Content<D>)>+'static;
+    type Puller: Pull<(T, C)>+'static;
     /// Alloctes a matched pair of push and pull endpoints implementing the pact.
-    fn connect<A: Allocate>(self, allocator: &mut A, identifier: usize) -> (Self::Pusher, Self::Puller);
+    ///
+    /// `address` is the path of the scope this pact is being instantiated within; none of the
+    /// pacts in this module read it.
+    ///
+    /// `logging` is an optional per-worker sink for `MessagesEvent`s; passing `None` disables
+    /// message logging on the send/receive path entirely rather than falling back to a global
+    /// logger.
+    fn connect<A: Allocate>(self, allocator: &mut A, identifier: usize, address: &[usize], logging: Option<Logger<MessagesEvent>>) -> (Self::Pusher, Self::Puller);
 }
 
 /// A direct connection
 pub struct Pipeline;
-impl<T: 'static, D: 'static> ParallelizationContract<T, D> for Pipeline {
-    type Pusher = Pusher<T, D>;
-    type Puller = Puller<T, D>;
-    fn connect<A: Allocate>(self, allocator: &mut A, identifier: usize) -> (Self::Pusher, Self::Puller) {
+impl<T: 'static, C: Container+'static> ParallelizationContract<T, C> for Pipeline {
+    type Pusher = Pusher<T, C>;
+    type Puller = Puller<T, C>;
+    fn connect<A: Allocate>(self, allocator: &mut A, identifier: usize, _address: &[usize], logging: Option<Logger<MessagesEvent>>) -> (Self::Pusher, Self::Puller) {
         // ignore &mut A and use thread allocator
-        let (mut pushers, puller) = Thread::new::<Message<T, D>>();
+        let (mut pushers, puller) = Thread::new::<Message<T, C>>();
 
-        (Pusher::new(pushers.pop().unwrap(), allocator.index(), allocator.index(), identifier),
-         Puller::new(puller, allocator.index(), identifier))
+        (Pusher::new(pushers.pop().unwrap(), allocator.index(), allocator.index(), identifier, logging.clone()),
+         Puller::new(puller, allocator.index(), identifier, logging))
     }
 }
 
@@ -56,13 +118,13 @@ impl<D, F: Fn(&D)->u64> Exchange<D, F> {
 // Exchange uses a Box<Pushable> because it cannot know what type of pushable will return from the allocator.
 // The PactObserver will do some buffering for Exchange, cutting down on the virtual calls, but we still
 // would like to get the vectors it sends back, so that they can be re-used if possible.
-impl<T: Eq+Data+Abomonation, D: Data+Abomonation, F: Fn(&D)->u64+'static> ParallelizationContract<T, D> for Exchange<D, F> {
-    type Pusher = Box<Push<(T, Content<D>)>>;
-    type Puller = Puller<T, D>;
-    fn connect<A: Allocate>(self, allocator: &mut A, identifier: usize) -> (Self::Pusher, Self::Puller) {
-        let (senders, receiver) = allocator.allocate::<Message<T, D>>();
-        let senders = senders.into_iter().enumerate().map(|(i,x)| Pusher::new(x, allocator.index(), i, identifier)).collect::<Vec<_>>();
-        (Box::new(ExchangePusher::new(senders, move |_, d| (self.hash_func)(d))), Puller::new(receiver, allocator.index(), identifier))
+impl<T: Eq+Data+Abomonation, D: Data+Abomonation, F: Fn(&D)->u64+'static> ParallelizationContract<T, Vec<D>> for Exchange<D, F> {
+    type Pusher = Box<Push<(T, Vec<D>)>>;
+    type Puller = Puller<T, Vec<D>>;
+    fn connect<A: Allocate>(self, allocator: &mut A, identifier: usize, _address: &[usize], logging: Option<Logger<MessagesEvent>>) -> (Self::Pusher, Self::Puller) {
+        let (senders, receiver) = allocator.allocate::<Message<T, Vec<D>>>();
+        let senders = senders.into_iter().enumerate().map(|(i,x)| Pusher::new(x, allocator.index(), i, identifier, logging.clone())).collect::<Vec<_>>();
+        (Box::new(ExchangePusher::new(senders, move |_: &T, d: &D| (self.hash_func)(d))), Puller::new(receiver, allocator.index(), identifier, logging))
     }
 }
 
@@ -78,49 +140,224 @@ impl<D, T, F: Fn(&T, &D)->u64> TimeExchange<D, T, F> {
     }
 }
 
-impl<T: Eq+Data+Abomonation, D: Data+Abomonation, F: Fn(&T, &D)->u64+'static> ParallelizationContract<T, D> for TimeExchange<D, T, F> {
-    type Pusher = ExchangePusher<T, D, Pusher<T, D>, F>;
-    type Puller = Puller<T, D>;
-    fn connect<A: Allocate>(self, allocator: &mut A, identifier: usize) -> (Self::Pusher, Self::Puller) {
-        let (senders, receiver) = allocator.allocate::<Message<T, D>>();
-        let senders = senders.into_iter().enumerate().map(|(i,x)| Pusher::new(x, allocator.index(), i, identifier)).collect::<Vec<_>>();
-        (ExchangePusher::new(senders, self.hash_func), Puller::new(receiver, allocator.index(), identifier))
+impl<T: Eq+Data+Abomonation, D: Data+Abomonation, F: Fn(&T, &D)->u64+'static> ParallelizationContract<T, Vec<D>> for TimeExchange<D, T, F> {
+    type Pusher = ExchangePusher<T, Vec<D>, F>;
+    type Puller = Puller<T, Vec<D>>;
+    fn connect<A: Allocate>(self, allocator: &mut A, identifier: usize, _address: &[usize], logging: Option<Logger<MessagesEvent>>) -> (Self::Pusher, Self::Puller) {
+        let (senders, receiver) = allocator.allocate::<Message<T, Vec<D>>>();
+        let senders = senders.into_iter().enumerate().map(|(i,x)| Pusher::new(x, allocator.index(), i, identifier, logging.clone())).collect::<Vec<_>>();
+        (ExchangePusher::new(senders, self.hash_func), Puller::new(receiver, allocator.index(), identifier, logging))
+    }
+}
+
+/// An exchange over many more logical partitions than there are workers, with the
+/// partition-to-worker assignment exposed for runtime rebalancing.
+pub struct Partition<D, F: Fn(&D)->u64+'static> {
+    hash_func: F,
+    parts: u64,
+    assignment: Rc<RefCell<Vec<usize>>>,
+    phantom: PhantomData<D>,
+}
+impl<D, F: Fn(&D)->u64> Partition<D, F> {
+    /// Allocates a new `Partition` pact with `parts` logical partitions. The partition-to-worker
+    /// assignment is filled in round-robin (`partition % peers`) once `connect` learns the real
+    /// peer count; until then `assignment()` returns an empty table.
+    pub fn new(hash_func: F, parts: usize) -> Partition<D, F> {
+        Partition {
+            hash_func: hash_func,
+            parts: parts as u64,
+            assignment: Rc::new(RefCell::new(Vec::new())),
+            phantom: PhantomData,
+        }
+    }
+    /// A shared handle to the partition-to-worker assignment table, which a control path can
+    /// mutate at runtime to reassign partitions between workers.
+    pub fn assignment(&self) -> Rc<RefCell<Vec<usize>>> {
+        self.assignment.clone()
+    }
+}
+
+impl<T: Eq+Data+Abomonation, D: Data+Abomonation, F: Fn(&D)->u64+'static> ParallelizationContract<T, Vec<D>> for Partition<D, F> {
+    type Pusher = PartitionPusher<T, D, F>;
+    type Puller = Puller<T, Vec<D>>;
+    fn connect<A: Allocate>(self, allocator: &mut A, identifier: usize, _address: &[usize], logging: Option<Logger<MessagesEvent>>) -> (Self::Pusher, Self::Puller) {
+        let (senders, receiver) = allocator.allocate::<Message<T, Vec<D>>>();
+        let peers = senders.len();
+
+        *self.assignment.borrow_mut() = (0..self.parts as usize).map(|partition| partition % peers).collect();
+
+        let senders = senders.into_iter().enumerate().map(|(i,x)| Pusher::new(x, allocator.index(), i, identifier, logging.clone())).collect::<Vec<_>>();
+        (PartitionPusher::new(senders, self.parts, self.hash_func, self.assignment), Puller::new(receiver, allocator.index(), identifier, logging))
+    }
+}
+
+/// Routes each record to the worker its logical partition is currently assigned to, consulting
+/// the shared assignment table on every push so reassignment takes effect immediately.
+pub struct PartitionPusher<T, D, F: Fn(&D)->u64+'static> {
+    senders: Vec<Pusher<T, Vec<D>>>,
+    parts: u64,
+    hash_func: F,
+    assignment: Rc<RefCell<Vec<usize>>>,
+    buffers: Vec<Vec<D>>,
+}
+impl<T, D, F: Fn(&D)->u64> PartitionPusher<T, D, F> {
+    /// Allocates a new `PartitionPusher` over `senders`, hashing records into `parts` logical
+    /// partitions via `hash_func` and resolving destinations through `assignment`.
+    pub fn new(senders: Vec<Pusher<T, Vec<D>>>, parts: u64, hash_func: F, assignment: Rc<RefCell<Vec<usize>>>) -> PartitionPusher<T, D, F> {
+        let buffers = senders.iter().map(|_| Vec::new()).collect();
+        PartitionPusher {
+            senders: senders,
+            parts: parts,
+            hash_func: hash_func,
+            assignment: assignment,
+            buffers: buffers,
+        }
+    }
+}
+
+impl<T: Clone, D, F: Fn(&D)->u64> Push<(T, Vec<D>)> for PartitionPusher<T, D, F> {
+    fn push(&mut self, message: &mut Option<(T, Vec<D>)>) {
+        if let Some((time, mut data)) = message.take() {
+            let parts = self.parts;
+            let assignment = self.assignment.borrow();
+            let hash_func = &self.hash_func;
+            let senders = &mut self.senders;
+            data.push_partitioned(
+                &mut self.buffers,
+                |item| assignment[((hash_func)(item) % parts) as usize],
+                |dest, buffer| {
+                    let mut to_send = Some((time.clone(), ::std::mem::replace(buffer, Vec::new())));
+                    senders[dest].push(&mut to_send);
+                },
+            );
+        }
+        else {
+            for sender in self.senders.iter_mut() {
+                sender.push(&mut None);
+            }
+        }
+    }
+}
+
+/// Partitions pushed batches across a fixed set of `Pusher`s by hashing `(time, record)` pairs.
+pub struct ExchangePusher<T, C: PushPartitioned, H> {
+    senders: Vec<Pusher<T, C>>,
+    buffers: Vec<C>,
+    hash_func: H,
+}
+impl<T, C: PushPartitioned+Default, H> ExchangePusher<T, C, H> {
+    /// Allocates a new `ExchangePusher` distributing over `senders` via `hash_func`.
+    pub fn new(senders: Vec<Pusher<T, C>>, hash_func: H) -> ExchangePusher<T, C, H> {
+        let buffers = (0..senders.len()).map(|_| C::default()).collect();
+        ExchangePusher {
+            senders: senders,
+            buffers: buffers,
+            hash_func: hash_func,
+        }
+    }
+}
+
+impl<T: Clone, C: PushPartitioned+Default, H: FnMut(&T, &C::Item)->u64> Push<(T, C)> for ExchangePusher<T, C, H> {
+    fn push(&mut self, message: &mut Option<(T, C)>) {
+        if let Some((time, mut data)) = message.take() {
+            let num_senders = self.senders.len();
+            let hash_func = &mut self.hash_func;
+            let senders = &mut self.senders;
+            data.push_partitioned(
+                &mut self.buffers,
+                |item| ((hash_func)(&time, item) % num_senders as u64) as usize,
+                |dest, buffer| {
+                    let mut to_send = Some((time.clone(), ::std::mem::replace(buffer, C::default())));
+                    senders[dest].push(&mut to_send);
+                },
+            );
+        }
+        else {
+            for sender in self.senders.iter_mut() {
+                sender.push(&mut None);
+            }
+        }
+    }
+}
+
+/// An exchange between multiple observers round-robin, independent of content.
+pub struct Distribute;
+impl<T: Eq+Data+Abomonation, C: Container+Data+Abomonation> ParallelizationContract<T, C> for Distribute {
+    type Pusher = DistributePusher<T, C>;
+    type Puller = Puller<T, C>;
+    fn connect<A: Allocate>(self, allocator: &mut A, identifier: usize, _address: &[usize], logging: Option<Logger<MessagesEvent>>) -> (Self::Pusher, Self::Puller) {
+        let (senders, receiver) = allocator.allocate::<Message<T, C>>();
+        let senders = senders.into_iter().enumerate().map(|(i,x)| Pusher::new(x, allocator.index(), i, identifier, logging.clone())).collect::<Vec<_>>();
+        (DistributePusher::new(senders), Puller::new(receiver, allocator.index(), identifier, logging))
+    }
+}
+
+/// Round-robins pushed batches across a fixed set of `Pusher`s, ignoring their content.
+pub struct DistributePusher<T, C> {
+    senders: Vec<Pusher<T, C>>,
+    next: usize,
+}
+impl<T, C> DistributePusher<T, C> {
+    /// Allocates a new `DistributePusher` cycling over `senders`.
+    pub fn new(senders: Vec<Pusher<T, C>>) -> DistributePusher<T, C> {
+        DistributePusher {
+            senders: senders,
+            next: 0,
+        }
+    }
+}
+
+impl<T, C: Container> Push<(T, C)> for DistributePusher<T, C> {
+    fn push(&mut self, message: &mut Option<(T, C)>) {
+        if message.is_some() {
+            self.senders[self.next].push(message);
+            self.next = (self.next + 1) % self.senders.len();
+        }
+        else {
+            for sender in self.senders.iter_mut() {
+                sender.push(&mut None);
+            }
+        }
     }
 }
 
-/// Wraps a `Message<T,D>` pusher to provide a `Push<(T, Content<D>)>`.
-pub struct Pusher<T, D> {
-    pusher: Box<Push<Message<T, D>>>,
+/// Wraps a `Message<T,C>` pusher to provide a `Push<(T, C)>`.
+pub struct Pusher<T, C> {
+    pusher: Box<Push<Message<T, C>>>,
     channel: usize,
     counter: usize,
     source: usize,
     target: usize,
+    logger: Option<Logger<MessagesEvent>>,
 }
-impl<T, D> Pusher<T, D> {
+impl<T, C> Pusher<T, C> {
     /// Allocates a new pusher.
-    pub fn new(pusher: Box<Push<Message<T, D>>>, source: usize, target: usize, channel: usize) -> Pusher<T, D> {
+    pub fn new(pusher: Box<Push<Message<T, C>>>, source: usize, target: usize, channel: usize, logger: Option<Logger<MessagesEvent>>) -> Pusher<T, C> {
         Pusher {
             pusher: pusher,
             channel: channel,
             counter: 0,
             source: source,
             target: target,
+            logger: logger,
         }
     }
 }
 
-impl<T, D> Push<(T, Content<D>)> for Pusher<T, D> {
-    fn push(&mut self, pair: &mut Option<(T, Content<D>)>) {
+impl<T, C: Container> Push<(T, C)> for Pusher<T, C> {
+    fn push(&mut self, pair: &mut Option<(T, C)>) {
         if let Some((time, data)) = pair.take() {
 
-            ::logging::log(&::logging::MESSAGES, ::logging::MessagesEvent {
-                is_send: true,
-                channel: self.channel,
-                source: self.source,
-                target: self.target,
-                seq_no: self.counter,
-                length: data.len(),
-            });
+            if let Some(ref l) = self.logger {
+                l.log(MessagesEvent {
+                    is_send: true,
+                    channel: self.channel,
+                    source: self.source,
+                    target: self.target,
+                    seq_no: self.counter,
+                    length: data.len(),
+                });
+            }
 
             let mut message = Some(Message::new(time, data, self.source, self.counter));
             self.counter += 1;
@@ -133,29 +370,31 @@ impl<T, D> Push<(T, Content<D>)> for Pusher<T, D> {
     }
 }
 
-/// Wraps a `Message<T,D>` puller to provide a `Pull<(T, Content<D>)>`.
-pub struct Puller<T, D> {
-    puller: Box<Pull<Message<T, D>>>,
-    current: Option<(T, Content<D>)>,
+/// Wraps a `Message<T,C>` puller to provide a `Pull<(T, C)>`.
+pub struct Puller<T, C> {
+    puller: Box<Pull<Message<T, C>>>,
+    current: Option<(T, C)>,
     channel: usize,
     counter: usize,
     index: usize,
+    logger: Option<Logger<MessagesEvent>>,
 }
-impl<T, D> Puller<T, D> {
+impl<T, C> Puller<T, C> {
     /// Allocates a new `Puller`.
-    pub fn new(puller: Box<Pull<Message<T, D>>>, index: usize, channel: usize) -> Puller<T, D> {
+    pub fn new(puller: Box<Pull<Message<T, C>>>, index: usize, channel: usize, logger: Option<Logger<MessagesEvent>>) -> Puller<T, C> {
         Puller {
             puller: puller,
             channel: channel,
             current: None,
             counter: 0,
             index: index,
+            logger: logger,
         }
     }
 }
 
-impl<T, D> Pull<(T, Content<D>)> for Puller<T, D> {
-    fn pull(&mut self) -> &mut Option<(T, Content<D>)> {
+impl<T, C: Container> Pull<(T, C)> for Puller<T, C> {
+    fn pull(&mut self) -> &mut Option<(T, C)> {
         let mut previous = self.current.take().map(|(time, data)| Message::new(time, data, self.index, self.counter));
         self.counter += 1;
 
@@ -163,17 +402,129 @@ impl<T, D> Pull<(T, Content<D>)> for Puller<T, D> {
 
         if let Some(ref message) = previous.as_ref() {
 
-            ::logging::log(&::logging::MESSAGES, ::logging::MessagesEvent {
-                is_send: false,
-                channel: self.channel,
-                source: message.from,
-                target: self.index,
-                seq_no: message.seq,
-                length: message.data.len(),
-            });
+            if let Some(ref l) = self.logger {
+                l.log(MessagesEvent {
+                    is_send: false,
+                    channel: self.channel,
+                    source: message.from,
+                    target: self.index,
+                    seq_no: message.seq,
+                    length: message.data.len(),
+                });
+            }
         }
 
         self.current = previous.map(|message| (message.time, message.data));
         &mut self.current
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `Push<Message<T, C>>` that records everything pushed to it, for use in place of the
+    /// allocator-provided sender a `Pusher` would normally wrap.
+    struct Recorder<T, C> {
+        sent: Rc<RefCell<Vec<Message<T, C>>>>,
+    }
+    impl<T, C> Push<Message<T, C>> for Recorder<T, C> {
+        fn push(&mut self, message: &mut Option<Message<T, C>>) {
+            if let Some(message) = message.take() {
+                self.sent.borrow_mut().push(message);
+            }
+        }
+    }
+
+    fn recording_pusher<T: 'static, C: 'static>(source: usize, target: usize, channel: usize) -> (Pusher<T, C>, Rc<RefCell<Vec<Message<T, C>>>>) {
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        let pusher = Pusher::new(Box::new(Recorder { sent: sent.clone() }), source, target, channel, None);
+        (pusher, sent)
+    }
+
+    #[test]
+    fn distribute_cycles_round_robin() {
+        let mut senders = Vec::new();
+        let mut sents = Vec::new();
+        for target in 0..3 {
+            let (pusher, sent) = recording_pusher::<usize, Vec<usize>>(0, target, 0);
+            senders.push(pusher);
+            sents.push(sent);
+        }
+
+        let mut distribute = DistributePusher::new(senders);
+        for i in 0..9 {
+            let mut message = Some((0usize, vec![i]));
+            distribute.push(&mut message);
+        }
+
+        for sent in &sents {
+            assert_eq!(sent.borrow().len(), 3);
+        }
+    }
+
+    #[test]
+    fn push_partitioned_flushes_at_capacity_and_on_final_sweep() {
+        let mut flushed = Vec::new();
+        let mut buffers = vec![Vec::new(), Vec::new()];
+
+        let mut data: Vec<usize> = (0..(PARTITION_BUFFER_SIZE + 1)).collect();
+        data.push(PARTITION_BUFFER_SIZE + 1); // lands in the other buffer, never fills it
+
+        data.push_partitioned(
+            &mut buffers,
+            |item| if *item <= PARTITION_BUFFER_SIZE { 0 } else { 1 },
+            |dest, buffer| {
+                flushed.push((dest, buffer.len()));
+                let _ = ::std::mem::replace(buffer, Vec::new());
+            },
+        );
+
+        // Buffer 0 fills exactly once mid-stream, then the final sweep flushes its 1 leftover
+        // record plus buffer 1's single record (which never reached capacity on its own).
+        assert_eq!(flushed, vec![(0, PARTITION_BUFFER_SIZE), (0, 1), (1, 1)]);
+        assert!(buffers.iter().all(Vec::is_empty));
+    }
+
+    #[test]
+    fn pusher_with_no_logger_still_forwards_messages() {
+        let (mut pusher, sent) = recording_pusher::<usize, Vec<usize>>(0, 1, 2);
+
+        let mut message = Some((7usize, vec![1, 2, 3]));
+        pusher.push(&mut message);
+
+        assert!(message.is_none());
+        let sent = sent.borrow();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].time, 7);
+        assert_eq!(sent[0].data, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn partition_reassignment_takes_effect_on_next_push() {
+        let mut senders = Vec::new();
+        let mut sents = Vec::new();
+        for target in 0..2 {
+            let (pusher, sent) = recording_pusher::<usize, Vec<usize>>(0, target, 0);
+            senders.push(pusher);
+            sents.push(sent);
+        }
+
+        // 2 logical partitions, both initially assigned to worker 0.
+        let assignment = Rc::new(RefCell::new(vec![0usize, 0usize]));
+        let mut partition = PartitionPusher::new(senders, 2, |d: &usize| *d as u64, assignment.clone());
+
+        // Hashes to partition 1, which starts out on worker 0.
+        let mut message = Some((0usize, vec![1usize]));
+        partition.push(&mut message);
+        assert_eq!(sents[0].borrow().len(), 1);
+        assert_eq!(sents[1].borrow().len(), 0);
+
+        // Reassign partition 1 to worker 1; the next push should route there immediately.
+        assignment.borrow_mut()[1] = 1;
+        let mut message = Some((0usize, vec![1usize]));
+        partition.push(&mut message);
+        assert_eq!(sents[0].borrow().len(), 1);
+        assert_eq!(sents[1].borrow().len(), 1);
+    }
+}